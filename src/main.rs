@@ -1,5 +1,7 @@
 use orbtk::prelude::*;
-use rand::{thread_rng, Rng};
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
+use std::collections::{HashSet, VecDeque};
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 enum BoardColor {
@@ -10,8 +12,7 @@ enum BoardColor {
     Empty,
 }
 impl BoardColor {
-    pub fn random() -> Self {
-        let mut rng = thread_rng();
+    pub fn random(rng: &mut StdRng) -> Self {
         let n: i32 = rng.gen_range(0..4);
         match n {
             0 => BoardColor::Red,
@@ -42,6 +43,17 @@ impl BoardColor {
             BoardColor::Empty => 'E',
         }
     }
+    /// The inverse of `get_glyph`. Returns `None` for anything that isn't one of the five glyphs.
+    pub fn from_glyph(c: char) -> Option<Self> {
+        match c {
+            'R' => Some(BoardColor::Red),
+            'B' => Some(BoardColor::Blue),
+            'G' => Some(BoardColor::Green),
+            'Y' => Some(BoardColor::Yellow),
+            'E' => Some(BoardColor::Empty),
+            _ => None,
+        }
+    }
 }
 /// The length of a side of the board.
 const BOARD_SIZE: usize = 10;
@@ -52,10 +64,19 @@ struct DotAction {
     y: usize,
 }
 
+/// A point-in-time snapshot of the parts of a board that change when a move is committed,
+/// used to implement undo/redo.
+#[derive(Clone, PartialEq, Eq)]
+struct BoardSnapshot {
+    dots: [BoardColor; BOARD_SIZE * BOARD_SIZE],
+    score: usize,
+    moves_left: usize,
+}
+
 /**
 The implementation  of a board state.
 */
-#[derive(PartialEq, Eq, AsAny)]
+#[derive(AsAny)]
 struct BoardState {
     /// Stores the board in a linear array
     dots: [BoardColor; BOARD_SIZE * BOARD_SIZE],
@@ -67,12 +88,117 @@ struct BoardState {
     score: usize,
     moves_left: usize,
     moused_over: Option<Entity>,
+    /// The text box "Save" writes a serialized board into and "Load" reads one back out of,
+    /// so a save can actually be copied out and shared, or a pasted-in one loaded.
+    save_text_box: Option<Entity>,
+    /// The generator backing every `BoardColor::random` draw made against this board.
+    rng: StdRng,
+    /// The seed `rng` was created from, so a board can be shared/reproduced as just a number.
+    seed: u64,
+    /// The trail last suggested by the "Hint" button, highlighted until the next click.
+    hint: Option<Vec<(usize, usize)>>,
+    /// Whether the game is still playable; recomputed every `update`.
+    status: GameStatus,
+    /// Snapshots taken right before each committed move, most recent last.
+    undo_stack: Vec<BoardSnapshot>,
+    /// Snapshots of the board as it stood right before each `undo`, most recent last,
+    /// available to `redo` until the next move.
+    redo_stack: Vec<BoardSnapshot>,
+    /// Every committed move, recorded in LURD-style notation by `finish_trail`; replayable
+    /// against a fresh board with the same seed via `replay`. Kept in lockstep with
+    /// `undo_stack`/`redo_stack` by `undo`/`redo`, so it lists the moves behind the live board.
+    /// `undo`/`redo` don't rewind `rng`, and `reset`/`load` replace the grid without reseeding,
+    /// so after an undo followed by a new move, a reset or a load, replaying `history` from
+    /// `seed` need not reproduce the live board's dots.
+    history: Vec<String>,
+    /// Moves popped off `history` by `undo`, available to `redo` until the next move.
+    redo_history: Vec<String>,
+}
+
+// `StdRng` isn't comparable, so equality only considers the board content, not how its
+// randomness was (or will be) generated.
+impl PartialEq for BoardState {
+    fn eq(&self, other: &Self) -> bool {
+        self.dots == other.dots
+            && self.trail == other.trail
+            && self.board_widgets == other.board_widgets
+            && self.score_label == other.score_label
+            && self.action == other.action
+            && self.score == other.score
+            && self.moves_left == other.moves_left
+            && self.moused_over == other.moused_over
+            && self.save_text_box == other.save_text_box
+            && self.hint == other.hint
+            && self.status == other.status
+            && self.undo_stack == other.undo_stack
+            && self.redo_stack == other.redo_stack
+            && self.history == other.history
+            && self.redo_history == other.redo_history
+    }
 }
+impl Eq for BoardState {}
 ///The number of moves that are allowed to be played in a single game.
 const MOVE_LIMIT: usize = 30;
 
+/// Whether the game can still be played.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum GameStatus {
+    /// Moves remain and there's at least one connectable pair on the board.
+    Playing,
+    /// `moves_left` hit zero.
+    OutOfMoves,
+    /// No two orthogonally adjacent cells share a color, so no trail can ever be started.
+    Stuck,
+}
+
+/// The ways that [`BoardState::deserialize`] can reject a saved board.
+#[derive(PartialEq, Eq, Debug)]
+pub enum ParseError {
+    /// The header line (score and moves left) was missing entirely.
+    MissingHeader,
+    /// The header line didn't contain two whitespace-separated numbers.
+    InvalidHeader,
+    /// The trail line contained something other than `x,y` pairs separated by `;`.
+    InvalidTrail,
+    /// A trail coordinate fell outside `0..BOARD_SIZE`.
+    TrailOutOfBounds(usize, usize),
+    /// A character in the grid wasn't one of `R`/`B`/`G`/`Y`/`E`.
+    InvalidGlyph(char),
+    /// The grid didn't have exactly `BOARD_SIZE` rows.
+    WrongRowCount(usize),
+    /// A row in the grid didn't have exactly `BOARD_SIZE` glyphs.
+    WrongRowLength(usize),
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::MissingHeader => write!(f, "missing header line"),
+            ParseError::InvalidHeader => write!(f, "header line must be \"score moves_left\""),
+            ParseError::InvalidTrail => write!(f, "trail line must be \"x,y;x,y;...\""),
+            ParseError::TrailOutOfBounds(x, y) => {
+                write!(f, "trail coordinate ({}, {}) is outside the board", x, y)
+            }
+            ParseError::InvalidGlyph(c) => write!(f, "'{}' is not a valid dot glyph", c),
+            ParseError::WrongRowCount(n) => {
+                write!(f, "expected {} rows, found {}", BOARD_SIZE, n)
+            }
+            ParseError::WrongRowLength(n) => {
+                write!(f, "expected rows of length {}, found {}", BOARD_SIZE, n)
+            }
+        }
+    }
+}
+
 impl BoardState {
     pub fn new() -> Self {
+        Self::from_seed(thread_rng().gen())
+    }
+
+    /// Build a board whose starting grid (and every re-roll in `fill_column`/`reset`
+    /// afterwards) is deterministically derived from `seed`, enabling a reproducible
+    /// "puzzle of the day".
+    pub fn from_seed(seed: u64) -> Self {
         let mut r: BoardState = BoardState {
             dots: [BoardColor::Green; BOARD_SIZE * BOARD_SIZE],
             trail: Vec::new(),
@@ -82,9 +208,18 @@ impl BoardState {
             score: 0,
             moves_left: MOVE_LIMIT,
             moused_over: None,
+            save_text_box: None,
+            rng: StdRng::seed_from_u64(seed),
+            seed,
+            hint: None,
+            status: GameStatus::Playing,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            history: Vec::new(),
+            redo_history: Vec::new(),
         };
         for i in 0..BOARD_SIZE * BOARD_SIZE {
-            r.dots[i] = BoardColor::random();
+            r.dots[i] = BoardColor::random(&mut r.rng);
         }
         r
     }
@@ -151,7 +286,7 @@ impl BoardState {
             }
         }
         for y in first_empty..BOARD_SIZE {
-            self.dots[Self::index(x, y)] = BoardColor::random();
+            self.dots[Self::index(x, y)] = BoardColor::random(&mut self.rng);
             if y == 0 {
                 continue;
             }
@@ -184,7 +319,7 @@ impl BoardState {
                 }
                 if roll_again {
                     println!("Rerolling! {}", roll);
-                    self.dots[Self::index(x, y)] = BoardColor::random();
+                    self.dots[Self::index(x, y)] = BoardColor::random(&mut self.rng);
                 }
             }
         }
@@ -223,6 +358,14 @@ impl BoardState {
         if self.trail.len() < 2 {
             return 0;
         }
+        self.undo_stack.push(BoardSnapshot {
+            dots: self.dots,
+            score: self.score,
+            moves_left: self.moves_left,
+        });
+        self.redo_stack.clear();
+        self.redo_history.clear();
+        self.history.push(Self::encode_move(&self.trail));
         let mut count: usize = 0;
         let trail_color = self.dots[Self::index(self.trail[0].0, self.trail[0].1)];
         //If the trail has a loop, clear the board of the color of the loop :)
@@ -250,7 +393,7 @@ impl BoardState {
         count
     }
     pub fn handle_click(&mut self, x: usize, y: usize) {
-        if self.moves_left == 0 {
+        if self.status != GameStatus::Playing {
             return;
         }
         if !self.trail.is_empty() {
@@ -271,12 +414,458 @@ impl BoardState {
     }
 
     pub fn reset(&mut self) {
+        let rng = &mut self.rng;
         self.dots.iter_mut().for_each(|b| {
-            *b = BoardColor::random();
+            *b = BoardColor::random(rng);
         });
         self.score = 0;
         self.trail.clear();
         self.moves_left = MOVE_LIMIT;
+        self.hint = None;
+        self.status = GameStatus::Playing;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.history.clear();
+        self.redo_history.clear();
+    }
+
+    /// Undo the last committed move, restoring the grid, score and moves left to how they
+    /// were beforehand. Returns `false` if there's nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        if let Some(previous) = self.undo_stack.pop() {
+            self.redo_stack.push(BoardSnapshot {
+                dots: self.dots,
+                score: self.score,
+                moves_left: self.moves_left,
+            });
+            if let Some(mv) = self.history.pop() {
+                self.redo_history.push(mv);
+            }
+            self.dots = previous.dots;
+            self.score = previous.score;
+            self.moves_left = previous.moves_left;
+            self.trail.clear();
+            self.hint = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Re-apply a move previously reverted by `undo`. Returns `false` if there's nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        if let Some(next) = self.redo_stack.pop() {
+            self.undo_stack.push(BoardSnapshot {
+                dots: self.dots,
+                score: self.score,
+                moves_left: self.moves_left,
+            });
+            if let Some(mv) = self.redo_history.pop() {
+                self.history.push(mv);
+            }
+            self.dots = next.dots;
+            self.score = next.score;
+            self.moves_left = next.moves_left;
+            self.trail.clear();
+            self.hint = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The LURD-style letter for a single orthogonal step from `a` to the adjacent cell `b`.
+    fn direction_between(a: (usize, usize), b: (usize, usize)) -> char {
+        let dx = b.0 as i32 - a.0 as i32;
+        let dy = b.1 as i32 - a.1 as i32;
+        match (dx, dy) {
+            (1, 0) => 'R',
+            (-1, 0) => 'L',
+            (0, 1) => 'D',
+            (0, -1) => 'U',
+            _ => panic!("trail steps must be single orthogonal moves"),
+        }
+    }
+
+    /// Encode a committed trail as its start cell plus a run of `L`/`R`/`U`/`D` steps between
+    /// consecutive positions, terminated by `!` (the confirm click that closes the move).
+    fn encode_move(trail: &[(usize, usize)]) -> String {
+        let (sx, sy) = trail[0];
+        let mut out = format!("{},{}", sx, sy);
+        for pair in trail.windows(2) {
+            out.push(Self::direction_between(pair[0], pair[1]));
+        }
+        out.push('!');
+        out
+    }
+
+    /// The inverse of `encode_move`: recovers the full trail of positions the move walked,
+    /// rejecting anything that would step outside `0..BOARD_SIZE`.
+    fn decode_move(mv: &str) -> Option<Vec<(usize, usize)>> {
+        let body = mv.strip_suffix('!')?;
+        let comma = body.find(',')?;
+        let rest = &body[comma + 1..];
+        let dir_start = rest
+            .find(|c: char| c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let sx: usize = body[..comma].parse().ok()?;
+        let sy: usize = rest[..dir_start].parse().ok()?;
+        if sx >= BOARD_SIZE || sy >= BOARD_SIZE {
+            return None;
+        }
+        let mut trail = vec![(sx, sy)];
+        for step in rest[dir_start..].chars() {
+            let &(x, y) = trail.last().expect("trail always has a start cell");
+            let next = match step {
+                'L' => (x.checked_sub(1)?, y),
+                'R' => (x + 1, y),
+                'U' => (x, y.checked_sub(1)?),
+                'D' => (x, y + 1),
+                _ => return None,
+            };
+            if next.0 >= BOARD_SIZE || next.1 >= BOARD_SIZE {
+                return None;
+            }
+            trail.push(next);
+        }
+        Some(trail)
+    }
+
+    /// Re-create the board `from_seed(seed)` would produce, then feed each recorded move
+    /// through `handle_click` in order, reproducing the exact same final board and score.
+    /// Stops early if `status` ever leaves `Playing` (out of moves, or stuck), the same way
+    /// `update` would have refused further clicks, so a move list longer than `MOVE_LIMIT`
+    /// or one that runs out the board can't drive `moves_left` below zero.
+    pub fn replay(seed: u64, moves: &[String]) -> Self {
+        let mut board = Self::from_seed(seed);
+        for mv in moves {
+            if board.status != GameStatus::Playing {
+                break;
+            }
+            if let Some(trail) = Self::decode_move(mv) {
+                for &(x, y) in &trail {
+                    board.handle_click(x, y);
+                }
+                if let Some(&(x, y)) = trail.last() {
+                    board.handle_click(x, y);
+                }
+            }
+            board.status = if board.moves_left == 0 {
+                GameStatus::OutOfMoves
+            } else if board.is_stuck() {
+                GameStatus::Stuck
+            } else {
+                GameStatus::Playing
+            };
+        }
+        board
+    }
+
+    /// True when no two orthogonally adjacent cells share a color, meaning no trail can
+    /// ever be started again.
+    pub fn is_stuck(&self) -> bool {
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                let idx = Self::index(x, y);
+                let color = self.dots[idx];
+                if color == BoardColor::Empty {
+                    continue;
+                }
+                if x + 1 < BOARD_SIZE && self.dots[Self::index(x + 1, y)] == color {
+                    return false;
+                }
+                if y + 1 < BOARD_SIZE && self.dots[Self::index(x, y + 1)] == color {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /**
+     * Serialize the board to a compact text format: a header line of
+     * `score moves_left`, a line of `x,y` trail positions separated by `;`,
+     * and then `BOARD_SIZE` rows of glyphs (see `BoardColor::get_glyph`).
+     */
+    pub fn serialize(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("{} {}\n", self.score, self.moves_left));
+        let trail: Vec<String> = self
+            .trail
+            .iter()
+            .map(|(x, y)| format!("{},{}", x, y))
+            .collect();
+        out.push_str(&trail.join(";"));
+        out.push('\n');
+        for y in 0..BOARD_SIZE {
+            let row: String = (0..BOARD_SIZE)
+                .map(|x| self.dots[Self::index(x, y)].get_glyph())
+                .collect();
+            out.push_str(&row);
+            out.push('\n');
+        }
+        out
+    }
+
+    /// The inverse of `serialize`. Reconstructs a full `BoardState`, with
+    /// everything besides the grid, trail, score and moves left reset to
+    /// its default (widgets are re-bound by `init` when the board is shown).
+    pub fn deserialize(s: &str) -> Result<BoardState, ParseError> {
+        let mut lines = s.lines();
+        let header = lines.next().ok_or(ParseError::MissingHeader)?;
+        let mut header_parts = header.split_whitespace();
+        let score: usize = header_parts
+            .next()
+            .and_then(|n| n.parse().ok())
+            .ok_or(ParseError::InvalidHeader)?;
+        let moves_left: usize = header_parts
+            .next()
+            .and_then(|n| n.parse().ok())
+            .ok_or(ParseError::InvalidHeader)?;
+
+        let trail_line = lines.next().unwrap_or("");
+        let mut trail = Vec::new();
+        if !trail_line.is_empty() {
+            for pos in trail_line.split(';') {
+                let mut coords = pos.split(',');
+                let x: usize = coords
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .ok_or(ParseError::InvalidTrail)?;
+                let y: usize = coords
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .ok_or(ParseError::InvalidTrail)?;
+                if x >= BOARD_SIZE || y >= BOARD_SIZE {
+                    return Err(ParseError::TrailOutOfBounds(x, y));
+                }
+                trail.push((x, y));
+            }
+        }
+
+        let rows: Vec<&str> = lines.collect();
+        if rows.len() != BOARD_SIZE {
+            return Err(ParseError::WrongRowCount(rows.len()));
+        }
+        let mut dots = [BoardColor::Empty; BOARD_SIZE * BOARD_SIZE];
+        for (y, row) in rows.iter().enumerate() {
+            let glyphs: Vec<char> = row.chars().collect();
+            if glyphs.len() != BOARD_SIZE {
+                return Err(ParseError::WrongRowLength(glyphs.len()));
+            }
+            for (x, c) in glyphs.into_iter().enumerate() {
+                dots[Self::index(x, y)] =
+                    BoardColor::from_glyph(c).ok_or(ParseError::InvalidGlyph(c))?;
+            }
+        }
+
+        // The saved text doesn't carry a seed (it's a snapshot, not a recipe), so further
+        // re-rolls are driven by a freshly seeded generator.
+        let seed: u64 = thread_rng().gen();
+        Ok(BoardState {
+            dots,
+            trail,
+            board_widgets: Vec::new(),
+            score_label: None,
+            action: None,
+            score,
+            moves_left,
+            moused_over: None,
+            save_text_box: None,
+            rng: StdRng::seed_from_u64(seed),
+            seed,
+            hint: None,
+            status: GameStatus::Playing,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            history: Vec::new(),
+            redo_history: Vec::new(),
+        })
+    }
+
+    /// Replace this board's grid, trail, score and moves left with a saved
+    /// board, leaving widgets and other UI-only state untouched. Clears the
+    /// undo/redo/history stacks and any pending hint the same way `reset` does,
+    /// since none of them describe the board being loaded in.
+    pub fn load(&mut self, s: &str) -> Result<(), ParseError> {
+        let loaded = Self::deserialize(s)?;
+        self.dots = loaded.dots;
+        self.trail = loaded.trail;
+        self.score = loaded.score;
+        self.moves_left = loaded.moves_left;
+        self.hint = None;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.history.clear();
+        self.redo_history.clear();
+        Ok(())
+    }
+
+    /// The orthogonal neighbors of `(x, y)` that are still on the board.
+    fn neighbors(x: usize, y: usize) -> Vec<(usize, usize)> {
+        let mut n = Vec::new();
+        if x > 0 {
+            n.push((x - 1, y));
+        }
+        if x + 1 < BOARD_SIZE {
+            n.push((x + 1, y));
+        }
+        if y > 0 {
+            n.push((x, y - 1));
+        }
+        if y + 1 < BOARD_SIZE {
+            n.push((x, y + 1));
+        }
+        n
+    }
+
+    /**
+     * Find the best available move: a trail of at least two dots that can be confirmed.
+     *
+     * Components of same-colored, 4-connected dots with at least as many internal adjacency
+     * edges as cells necessarily contain a cycle (per `has_loop`/`finish_trail`, closing such a
+     * trail clears every dot of that color), so those win outright; the first such cycle found
+     * is returned, without comparing how many dots each color would clear. Otherwise this
+     * falls back to the longest simple same-color path found by bounded DFS.
+     */
+    pub fn best_move(&self) -> Option<Vec<(usize, usize)>> {
+        let mut visited = [false; BOARD_SIZE * BOARD_SIZE];
+        let mut components: Vec<(BoardColor, Vec<(usize, usize)>)> = Vec::new();
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                let idx = Self::index(x, y);
+                if visited[idx] || self.dots[idx] == BoardColor::Empty {
+                    continue;
+                }
+                let color = self.dots[idx];
+                let mut component = Vec::new();
+                let mut queue = VecDeque::new();
+                queue.push_back((x, y));
+                visited[idx] = true;
+                while let Some(pos) = queue.pop_front() {
+                    component.push(pos);
+                    for (nx, ny) in Self::neighbors(pos.0, pos.1) {
+                        let nidx = Self::index(nx, ny);
+                        if !visited[nidx] && self.dots[nidx] == color {
+                            visited[nidx] = true;
+                            queue.push_back((nx, ny));
+                        }
+                    }
+                }
+                components.push((color, component));
+            }
+        }
+
+        // A cycle clears every dot of its color off the board, so any component that has
+        // one beats every path-only component; take the first one found.
+        for (color, component) in &components {
+            if component.len() < 2 {
+                continue;
+            }
+            let edges: usize = component
+                .iter()
+                .flat_map(|&(cx, cy)| Self::neighbors(cx, cy))
+                .filter(|&(nx, ny)| self.dots[Self::index(nx, ny)] == *color)
+                .count()
+                / 2;
+            if edges >= component.len() {
+                if let Some(cycle) = Self::find_cycle(&self.dots, component[0], *color) {
+                    return Some(cycle);
+                }
+            }
+        }
+
+        components
+            .iter()
+            .map(|(color, component)| Self::longest_path(&self.dots, component, *color))
+            .filter(|path| path.len() >= 2)
+            .max_by_key(|path| path.len())
+    }
+
+    /// DFS from `start` over same-`color` cells, returning the first cycle found as a trail
+    /// (its first and last positions repeat, matching what `has_loop` expects).
+    fn find_cycle(
+        dots: &[BoardColor; BOARD_SIZE * BOARD_SIZE],
+        start: (usize, usize),
+        color: BoardColor,
+    ) -> Option<Vec<(usize, usize)>> {
+        let mut path = Vec::new();
+        let mut on_path = HashSet::new();
+        Self::dfs_cycle(dots, start, None, &mut path, &mut on_path, color)
+    }
+
+    fn dfs_cycle(
+        dots: &[BoardColor; BOARD_SIZE * BOARD_SIZE],
+        current: (usize, usize),
+        parent: Option<(usize, usize)>,
+        path: &mut Vec<(usize, usize)>,
+        on_path: &mut HashSet<(usize, usize)>,
+        color: BoardColor,
+    ) -> Option<Vec<(usize, usize)>> {
+        path.push(current);
+        on_path.insert(current);
+        for (nx, ny) in Self::neighbors(current.0, current.1) {
+            let next = (nx, ny);
+            if Some(next) == parent || dots[Self::index(nx, ny)] != color {
+                continue;
+            }
+            if on_path.contains(&next) {
+                let start_idx = path.iter().position(|&p| p == next).expect("on_path implies present in path");
+                let mut cycle: Vec<(usize, usize)> = path[start_idx..].to_vec();
+                cycle.push(next);
+                return Some(cycle);
+            }
+            if let Some(found) = Self::dfs_cycle(dots, next, Some(current), path, on_path, color) {
+                return Some(found);
+            }
+        }
+        path.pop();
+        on_path.remove(&current);
+        None
+    }
+
+    /// The longest recursion depth the path search will explore from a single start cell,
+    /// to keep the bounded DFS from blowing up on a large same-color component.
+    const HINT_SEARCH_DEPTH_CAP: usize = 20;
+
+    /// The longest simple same-color path reachable from any cell in `component`.
+    fn longest_path(
+        dots: &[BoardColor; BOARD_SIZE * BOARD_SIZE],
+        component: &[(usize, usize)],
+        color: BoardColor,
+    ) -> Vec<(usize, usize)> {
+        let mut best: Vec<(usize, usize)> = Vec::new();
+        for &start in component {
+            let mut visited = HashSet::new();
+            let mut path = Vec::new();
+            Self::dfs_longest(dots, start, &mut visited, &mut path, &mut best, color);
+        }
+        best
+    }
+
+    fn dfs_longest(
+        dots: &[BoardColor; BOARD_SIZE * BOARD_SIZE],
+        current: (usize, usize),
+        visited: &mut HashSet<(usize, usize)>,
+        path: &mut Vec<(usize, usize)>,
+        best: &mut Vec<(usize, usize)>,
+        color: BoardColor,
+    ) {
+        visited.insert(current);
+        path.push(current);
+        if path.len() > best.len() {
+            *best = path.clone();
+        }
+        if path.len() < Self::HINT_SEARCH_DEPTH_CAP {
+            for (nx, ny) in Self::neighbors(current.0, current.1) {
+                let next = (nx, ny);
+                if !visited.contains(&next) && dots[Self::index(nx, ny)] == color {
+                    Self::dfs_longest(dots, next, visited, path, best, color);
+                }
+            }
+        }
+        path.pop();
+        visited.remove(&current);
     }
 }
 impl Default for BoardState {
@@ -288,6 +877,32 @@ impl Default for BoardState {
 #[cfg(test)]
 mod test {
     use crate::*;
+
+    /// A board painted entirely Blue except for a 2x2 Red loop at (0,0)-(1,1), used by
+    /// the solver and undo/redo tests that need a move they know will clear the board.
+    fn board_with_red_2x2_loop(seed: u64) -> BoardState {
+        let mut board = BoardState::from_seed(seed);
+        for c in board.dots.iter_mut() {
+            *c = BoardColor::Blue;
+        }
+        board.dots[BoardState::index(0, 0)] = BoardColor::Red;
+        board.dots[BoardState::index(1, 0)] = BoardColor::Red;
+        board.dots[BoardState::index(0, 1)] = BoardColor::Red;
+        board.dots[BoardState::index(1, 1)] = BoardColor::Red;
+        board
+    }
+
+    /// Walk `board_with_red_2x2_loop`'s loop corner by corner, then re-click the start to
+    /// confirm the move, exactly as a player closing that loop would click it.
+    fn click_red_2x2_loop(board: &mut BoardState) {
+        board.handle_click(0, 0);
+        board.handle_click(1, 0);
+        board.handle_click(1, 1);
+        board.handle_click(0, 1);
+        board.handle_click(0, 0);
+        board.handle_click(0, 0);
+    }
+
     #[test]
     pub fn test_drop() {
         let mut board = BoardState::new();
@@ -299,6 +914,290 @@ mod test {
         assert!(board.has_loop());
         assert_ne!(board.finish_trail(), 0);
     }
+
+    #[test]
+    pub fn test_serialize_round_trip() {
+        let board = BoardState::new();
+        let text = board.serialize();
+        let loaded = BoardState::deserialize(&text).expect("should parse a freshly serialized board");
+        assert!(loaded == board);
+    }
+
+    #[test]
+    pub fn test_serialize_round_trip_with_trail() {
+        let mut board = BoardState::new();
+        board.trail.push((0, 0));
+        board.trail.push((0, 1));
+        let text = board.serialize();
+        let loaded = BoardState::deserialize(&text).expect("should parse a board with a trail");
+        assert!(loaded == board);
+    }
+
+    #[test]
+    pub fn test_deserialize_rejects_bad_glyph() {
+        let mut text = String::from("0 30\n\n");
+        for _ in 0..BOARD_SIZE {
+            text.push_str(&"E".repeat(BOARD_SIZE - 1));
+            text.push_str("Q\n");
+        }
+        assert!(matches!(
+            BoardState::deserialize(&text),
+            Err(ParseError::InvalidGlyph('Q'))
+        ));
+    }
+
+    #[test]
+    pub fn test_deserialize_rejects_out_of_bounds_trail() {
+        let mut text = String::from("0 30\n15,15\n");
+        for _ in 0..BOARD_SIZE {
+            text.push_str(&"E".repeat(BOARD_SIZE));
+            text.push('\n');
+        }
+        assert!(matches!(
+            BoardState::deserialize(&text),
+            Err(ParseError::TrailOutOfBounds(15, 15))
+        ));
+    }
+
+    #[test]
+    pub fn test_same_seed_reproduces_board() {
+        let a = BoardState::from_seed(1234);
+        let b = BoardState::from_seed(1234);
+        assert_eq!(a.dots, b.dots);
+        assert_eq!(a.seed, b.seed);
+    }
+
+    #[test]
+    pub fn test_same_seed_reproduces_fill_column() {
+        let mut a = BoardState::from_seed(5678);
+        let mut b = BoardState::from_seed(5678);
+        // Empties sit at the top of a column (highest y) once dropped, so that's the cell
+        // fill_column re-rolls.
+        a.dots[BoardState::index(0, BOARD_SIZE - 1)] = BoardColor::Empty;
+        b.dots[BoardState::index(0, BOARD_SIZE - 1)] = BoardColor::Empty;
+        a.fill_column(0);
+        b.fill_column(0);
+        assert_eq!(a.dots, b.dots);
+    }
+
+    #[test]
+    pub fn test_best_move_finds_loop() {
+        let mut board = board_with_red_2x2_loop(42);
+        let mv = board.best_move().expect("should find the 2x2 loop");
+        board.trail = mv;
+        assert!(board.has_loop());
+    }
+
+    #[test]
+    pub fn test_best_move_falls_back_to_longest_path() {
+        let mut board = BoardState::from_seed(7);
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                board.dots[BoardState::index(x, y)] = if (x + y) % 2 == 0 {
+                    BoardColor::Blue
+                } else {
+                    BoardColor::Green
+                };
+            }
+        }
+        board.dots[BoardState::index(0, 0)] = BoardColor::Red;
+        board.dots[BoardState::index(1, 0)] = BoardColor::Red;
+        board.dots[BoardState::index(2, 0)] = BoardColor::Red;
+        let mv = board.best_move().expect("should find the 3-cell red path");
+        assert_eq!(mv.len(), 3);
+    }
+
+    #[test]
+    pub fn test_best_move_none_when_nothing_connects() {
+        let mut board = BoardState::from_seed(7);
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                board.dots[BoardState::index(x, y)] = if (x + y) % 2 == 0 {
+                    BoardColor::Blue
+                } else {
+                    BoardColor::Green
+                };
+            }
+        }
+        assert_eq!(board.best_move(), None);
+    }
+
+    #[test]
+    pub fn test_is_stuck_true_on_checkerboard() {
+        let mut board = BoardState::from_seed(7);
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                board.dots[BoardState::index(x, y)] = if (x + y) % 2 == 0 {
+                    BoardColor::Blue
+                } else {
+                    BoardColor::Green
+                };
+            }
+        }
+        assert!(board.is_stuck());
+    }
+
+    #[test]
+    pub fn test_is_stuck_false_with_adjacent_pair() {
+        let mut board = BoardState::from_seed(7);
+        board.dots[BoardState::index(0, 0)] = BoardColor::Red;
+        board.dots[BoardState::index(1, 0)] = BoardColor::Red;
+        assert!(!board.is_stuck());
+    }
+
+    #[test]
+    pub fn test_handle_click_ignored_once_out_of_moves() {
+        let mut board = BoardState::from_seed(7);
+        board.moves_left = 0;
+        board.status = GameStatus::OutOfMoves;
+        board.handle_click(0, 0);
+        assert!(board.trail.is_empty());
+    }
+
+    #[test]
+    pub fn test_undo_restores_dots_score_and_moves_left() {
+        let mut board = board_with_red_2x2_loop(42);
+
+        let dots_before = board.dots;
+        let score_before = board.score;
+        let moves_before = board.moves_left;
+
+        click_red_2x2_loop(&mut board);
+        assert_ne!(board.dots, dots_before);
+        assert_ne!(board.score, score_before);
+        assert_ne!(board.moves_left, moves_before);
+
+        assert!(board.undo());
+        assert_eq!(board.dots, dots_before);
+        assert_eq!(board.score, score_before);
+        assert_eq!(board.moves_left, moves_before);
+        assert!(!board.undo());
+    }
+
+    #[test]
+    pub fn test_redo_reapplies_an_undone_move() {
+        let mut board = board_with_red_2x2_loop(42);
+
+        click_red_2x2_loop(&mut board);
+        let dots_after_move = board.dots;
+        let score_after_move = board.score;
+        let moves_after_move = board.moves_left;
+
+        assert!(board.undo());
+        assert!(board.redo());
+        assert_eq!(board.dots, dots_after_move);
+        assert_eq!(board.score, score_after_move);
+        assert_eq!(board.moves_left, moves_after_move);
+    }
+
+    #[test]
+    pub fn test_undo_keeps_history_in_sync_with_the_board() {
+        let mut board = board_with_red_2x2_loop(42);
+
+        click_red_2x2_loop(&mut board);
+        assert_eq!(board.history.len(), 1);
+
+        assert!(board.undo());
+        assert!(
+            board.history.is_empty(),
+            "undoing the only move should drop it from history, not leave it there to replay"
+        );
+
+        assert!(board.redo());
+        assert_eq!(board.history.len(), 1);
+    }
+
+    #[test]
+    pub fn test_load_clears_undo_redo_history() {
+        let mut board = board_with_red_2x2_loop(42);
+        click_red_2x2_loop(&mut board);
+        assert_eq!(board.undo_stack.len(), 1);
+        assert_eq!(board.history.len(), 1);
+
+        let other = BoardState::new();
+        let saved = other.serialize();
+        board.load(&saved).expect("should parse a freshly serialized board");
+
+        assert!(
+            board.undo_stack.is_empty(),
+            "loading a board shouldn't leave behind an undo entry for the board it replaced"
+        );
+        assert!(board.redo_stack.is_empty());
+        assert!(board.history.is_empty());
+        assert!(board.redo_history.is_empty());
+        assert!(
+            !board.undo(),
+            "undo after load must not revert to the board that was replaced"
+        );
+    }
+
+    #[test]
+    pub fn test_move_notation_round_trips() {
+        let trail = vec![(2, 3), (3, 3), (3, 4), (2, 4)];
+        let encoded = BoardState::encode_move(&trail);
+        assert_eq!(BoardState::decode_move(&encoded), Some(trail));
+    }
+
+    #[test]
+    pub fn test_replay_reproduces_final_board() {
+        let seed = 99;
+        let mut board = BoardState::from_seed(seed);
+        let mv = board.best_move().expect("a fresh board should have a move");
+        for &(x, y) in &mv {
+            board.handle_click(x, y);
+        }
+        let (lx, ly) = *mv.last().expect("best_move trails have at least 2 cells");
+        board.handle_click(lx, ly);
+
+        let replayed = BoardState::replay(seed, &board.history);
+        assert_eq!(replayed.dots, board.dots);
+        assert_eq!(replayed.score, board.score);
+        assert_eq!(replayed.moves_left, board.moves_left);
+    }
+
+    #[test]
+    pub fn test_decode_move_rejects_steps_that_walk_off_the_board() {
+        // Starts at the right edge and steps right, which would land outside BOARD_SIZE.
+        let mv = format!("{},0R!", BOARD_SIZE - 1);
+        assert_eq!(BoardState::decode_move(&mv), None);
+    }
+
+    #[test]
+    pub fn test_replay_ignores_moves_past_move_limit() {
+        // Play a real game out (status leaves `Playing` once moves run out or the board
+        // gets stuck), recording the exact same status update `update()` would perform.
+        let seed = 99;
+        let mut board = BoardState::from_seed(seed);
+        while board.status == GameStatus::Playing {
+            let Some(mv) = board.best_move() else {
+                break;
+            };
+            for &(x, y) in &mv {
+                board.handle_click(x, y);
+            }
+            let (lx, ly) = *mv.last().expect("best_move trails have at least 2 cells");
+            board.handle_click(lx, ly);
+            board.status = if board.moves_left == 0 {
+                GameStatus::OutOfMoves
+            } else if board.is_stuck() {
+                GameStatus::Stuck
+            } else {
+                GameStatus::Playing
+            };
+        }
+        let moves_left_after_real_play = board.moves_left;
+
+        // Append bogus extra moves far beyond what a real game would ever produce.
+        let mut padded_history = board.history.clone();
+        for _ in 0..10 {
+            padded_history.push(BoardState::encode_move(&[(0, 0), (0, 1)]));
+        }
+
+        // Should stop applying moves once status leaves Playing instead of underflowing
+        // moves_left on the extras.
+        let replayed = BoardState::replay(seed, &padded_history);
+        assert_eq!(replayed.moves_left, moves_left_after_real_play);
+    }
 }
 
 widget!(DotBoard<BoardState>: MouseHandler {
@@ -321,16 +1220,39 @@ impl State for BoardState {
             ctx.entity_of_child("score_label")
                 .expect("Couldn't find score label"),
         );
+        self.save_text_box = Some(
+            ctx.entity_of_child("save_text_box")
+                .expect("Couldn't find save text box"),
+        );
         self.update(_reg, ctx);
     }
     fn update(&mut self, _reg: &mut Registry, ctx: &mut Context) {
         if let Some(ac) = self.action {
             self.handle_click(ac.x, ac.y);
             self.action = None;
+            self.hint = None;
         }
+        self.status = if self.moves_left == 0 {
+            GameStatus::OutOfMoves
+        } else if self.is_stuck() {
+            GameStatus::Stuck
+        } else {
+            GameStatus::Playing
+        };
+        // Scoped down from a dedicated "Final score" overlay panel: the game-over text
+        // just replaces score_label's contents in place rather than adding a separate widget.
         let mut score_label = ctx.get_widget(self.score_label.expect("Failed to find label"));
         let text = score_label.get_mut::<String>("text");
-        *text = format!("Score: {}, moves left: {}", self.score, self.moves_left);
+        *text = match self.status {
+            GameStatus::Playing => format!(
+                "Score: {}, moves left: {}, seed: {}",
+                self.score, self.moves_left, self.seed
+            ),
+            GameStatus::OutOfMoves | GameStatus::Stuck => format!(
+                "Final score: {} (seed: {}) -- click Reset to play again",
+                self.score, self.seed
+            ),
+        };
         for x in 0..BOARD_SIZE {
             for y in 0..BOARD_SIZE {
                 let idx = Self::index(x, y);
@@ -338,6 +1260,8 @@ impl State for BoardState {
 
                 let bc = self.dots[idx];
                 ctx.get_widget(en).set("background", bc.get_brush());
+                ctx.get_widget(en)
+                    .set("enabled", self.status == GameStatus::Playing);
                 let mut text: String = format!("{}", bc.get_glyph());
                 let id = ctx.get_widget(en).get::<String>("id").clone();
                 if self
@@ -358,6 +1282,15 @@ impl State for BoardState {
                         ctx.get_widget(en)
                             .set("border_brush", Brush::from("#ff00ff"));
                     }
+                } else if self
+                    .hint
+                    .as_ref()
+                    .map_or(false, |h| h.contains(&(x, y)))
+                {
+                    text = format!("{}(?)", text);
+                    ctx.get_widget(en).set("border_width", Thickness::from(2.0));
+                    ctx.get_widget(en)
+                        .set("border_brush", Brush::from("#9400d3"));
                 } else if self.can_connect(x, y) {
                     text = format!("{}(A)", text);
                     // ctx.get_widget(en).set("text", "sure".to_string());
@@ -429,9 +1362,12 @@ impl Template for DotBoard {
         // which doesn't happen to look especially square
         let new_grid = Grid::new()
             .columns(Blocks::create().push(Block::create().size(BlockSize::Auto).build()))
-            .rows(Blocks::create().repeat(Block::create().size(BlockSize::Auto).build(), 3))
+            .rows(Blocks::create().repeat(Block::create().size(BlockSize::Auto).build(), 9))
             .place(ctx, grid, 0, 0)
             .place(ctx, TextBlock::new().id("score_label"), 0, 1)
+            // "Save" writes a serialized board in here and "Load" reads whatever is
+            // currently in here, so a save can be copied out and a pasted-in one loaded.
+            .place(ctx, TextBox::new().id("save_text_box").water_mark("Save or paste a board here"), 0, 2)
             .place(
                 ctx,
                 Button::new()
@@ -443,7 +1379,85 @@ impl Template for DotBoard {
                     .attach(Grid::column_span(1))
                     .h_align(Alignment::Center),
                 0,
-                2,
+                3,
+            )
+            .place(
+                ctx,
+                Button::new()
+                    .text("Save")
+                    .on_click(move |a, _b| {
+                        let text = a.get_mut::<BoardState>(id).serialize();
+                        let save_text_box = a
+                            .get_mut::<BoardState>(id)
+                            .save_text_box
+                            .expect("save_text_box not bound yet");
+                        *a.get_widget(save_text_box).get_mut::<String>("text") = text;
+                        true
+                    })
+                    .attach(Grid::column_span(1))
+                    .h_align(Alignment::Center),
+                0,
+                4,
+            )
+            .place(
+                ctx,
+                Button::new()
+                    .text("Load")
+                    .on_click(move |a, _b| {
+                        let save_text_box = a
+                            .get_mut::<BoardState>(id)
+                            .save_text_box
+                            .expect("save_text_box not bound yet");
+                        let text = a.get_widget(save_text_box).get::<String>("text").clone();
+                        if let Err(e) = a.get_mut::<BoardState>(id).load(&text) {
+                            println!("Couldn't load saved board: {}", e);
+                        }
+                        true
+                    })
+                    .attach(Grid::column_span(1))
+                    .h_align(Alignment::Center),
+                0,
+                5,
+            )
+            .place(
+                ctx,
+                Button::new()
+                    .text("Hint")
+                    .on_click(move |a, _b| {
+                        let state = a.get_mut::<BoardState>(id);
+                        state.hint = state.best_move();
+                        true
+                    })
+                    .attach(Grid::column_span(1))
+                    .h_align(Alignment::Center),
+                0,
+                6,
+            )
+            .place(
+                ctx,
+                Button::new()
+                    .text("Undo")
+                    .on_click(move |a, _b| {
+                        a.get_mut::<BoardState>(id).undo();
+                        true
+                    })
+                    .attach(Grid::column_span(1))
+                    .h_align(Alignment::Center),
+                0,
+                7,
+            )
+            .place(
+                ctx,
+                Button::new()
+                    .text("Redo")
+                    .on_click(move |a, _b| {
+                        a.get_mut::<BoardState>(id).redo();
+                        true
+                    })
+                    .attach(Grid::column_span(1))
+                    .h_align(Alignment::Center),
+                0,
+                8,
             );
         self.name("DotBoard").child(
             Container::new()